@@ -0,0 +1,202 @@
+//! Client side of the parse service.
+//!
+//! The old `MixedCombinedParser` blocked on a socket, reading until EOF, which
+//! cannot coexist with an editor event loop. This module splits the client
+//! into a blocking trait ([`SyncParseClient`]) and a non-blocking trait
+//! ([`AsyncParseClient`]), combined as [`ParseClient`]. The default TCP
+//! implementation exposes the underlying descriptor via [`AsRawFd`] (and
+//! `AsRawSocket` on Windows) so callers can register it in a `poll`/`select`
+//! loop and drain replies with [`AsyncParseClient::poll_for_response`].
+//!
+//! Requests and replies are length-framed with a `Content-Length` header
+//! followed by the body: the request body is the Ada source text, the reply
+//! body is a serialized [`AstNode`].
+
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, RawFd};
+#[cfg(windows)]
+use std::os::windows::io::{AsRawSocket, RawSocket};
+
+use crate::AstNode;
+
+/// Identifies an in-flight asynchronous parse request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestId(pub u64);
+
+/// Blocking client: send a request and wait for the parsed reply.
+pub trait SyncParseClient {
+    /// Build a request for `source`, send it, and block until the reply AST
+    /// has been received.
+    fn parse_blocking(&mut self, source: &str) -> std::io::Result<AstNode>;
+}
+
+/// Non-blocking client: fire a request and poll for its reply later.
+pub trait AsyncParseClient {
+    /// Send a request for `source` and return immediately with its id.
+    fn parse_async(&mut self, source: &str) -> std::io::Result<RequestId>;
+
+    /// Return a reply AST if a complete framed message is buffered, or `None`
+    /// if nothing has arrived yet. Never blocks.
+    fn poll_for_response(&mut self) -> Option<AstNode>;
+}
+
+/// The full client surface.
+pub trait ParseClient: SyncParseClient + AsyncParseClient {}
+
+impl<T: SyncParseClient + AsyncParseClient> ParseClient for T {}
+
+/// Default TCP implementation, connecting to the `127.0.0.1` handoff.
+pub struct TcpParseClient {
+    stream: TcpStream,
+    /// Bytes received but not yet framed into a complete reply.
+    inbox: Vec<u8>,
+    next_id: u64,
+}
+
+impl TcpParseClient {
+    /// Connect to a parse server (e.g. `("127.0.0.1", 46000)`).
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> std::io::Result<TcpParseClient> {
+        let stream = TcpStream::connect(addr)?;
+        Ok(TcpParseClient { stream, inbox: Vec::new(), next_id: 1 })
+    }
+
+    fn send(&mut self, source: &str) -> std::io::Result<()> {
+        self.stream.write_all(&encode_frame(source.as_bytes()))?;
+        self.stream.flush()
+    }
+}
+
+impl SyncParseClient for TcpParseClient {
+    fn parse_blocking(&mut self, source: &str) -> std::io::Result<AstNode> {
+        self.stream.set_nonblocking(false)?;
+        self.send(source)?;
+        // Read (possibly in chunks) until a full frame is buffered.
+        let mut chunk = [0u8; 4096];
+        loop {
+            if let Some(body) = take_frame(&mut self.inbox) {
+                return decode_ast(&body);
+            }
+            let n = self.stream.read(&mut chunk)?;
+            if n == 0 {
+                return Err(std::io::Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "connection closed before a complete reply",
+                ));
+            }
+            self.inbox.extend_from_slice(&chunk[..n]);
+        }
+    }
+}
+
+impl AsyncParseClient for TcpParseClient {
+    fn parse_async(&mut self, source: &str) -> std::io::Result<RequestId> {
+        self.stream.set_nonblocking(true)?;
+        self.send(source)?;
+        let id = RequestId(self.next_id);
+        self.next_id += 1;
+        Ok(id)
+    }
+
+    fn poll_for_response(&mut self) -> Option<AstNode> {
+        // A fresh connection or a preceding `parse_blocking` leaves the socket
+        // in blocking mode, so switch it before reading.
+        if self.stream.set_nonblocking(true).is_err() {
+            return None;
+        }
+        // Drain whatever is currently readable without blocking.
+        let mut chunk = [0u8; 4096];
+        loop {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(n) => self.inbox.extend_from_slice(&chunk[..n]),
+                Err(ref e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(_) => break,
+            }
+        }
+        take_frame(&mut self.inbox).and_then(|body| decode_ast(&body).ok())
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for TcpParseClient {
+    fn as_raw_fd(&self) -> RawFd {
+        self.stream.as_raw_fd()
+    }
+}
+
+#[cfg(windows)]
+impl AsRawSocket for TcpParseClient {
+    fn as_raw_socket(&self) -> RawSocket {
+        self.stream.as_raw_socket()
+    }
+}
+
+/// Frame `body` with a `Content-Length` header.
+fn encode_frame(body: &[u8]) -> Vec<u8> {
+    let mut out = format!("Content-Length: {}\r\n\r\n", body.len()).into_bytes();
+    out.extend_from_slice(body);
+    out
+}
+
+/// If `buf` holds at least one complete frame, remove and return its body.
+fn take_frame(buf: &mut Vec<u8>) -> Option<Vec<u8>> {
+    const SEP: &[u8] = b"\r\n\r\n";
+    let header_end = buf.windows(SEP.len()).position(|w| w == SEP)?;
+    let header = std::str::from_utf8(&buf[..header_end]).ok()?;
+    let len: usize = header
+        .lines()
+        .find_map(|l| l.strip_prefix("Content-Length:"))
+        .and_then(|v| v.trim().parse().ok())?;
+    let body_start = header_end + SEP.len();
+    if buf.len() < body_start + len {
+        return None; // body not fully buffered yet
+    }
+    let body = buf[body_start..body_start + len].to_vec();
+    buf.drain(..body_start + len);
+    Some(body)
+}
+
+fn decode_ast(body: &[u8]) -> std::io::Result<AstNode> {
+    serde_json::from_slice(body)
+        .map_err(|e| std::io::Error::new(ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_ada_to_ast;
+
+    #[test]
+    fn frame_roundtrips_through_take_frame() {
+        let mut buf = encode_frame(b"hello");
+        assert_eq!(take_frame(&mut buf), Some(b"hello".to_vec()));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn take_frame_waits_for_full_body() {
+        let mut buf = b"Content-Length: 5\r\n\r\nhel".to_vec();
+        assert_eq!(take_frame(&mut buf), None);
+        buf.extend_from_slice(b"lo");
+        assert_eq!(take_frame(&mut buf), Some(b"hello".to_vec()));
+    }
+
+    #[test]
+    fn take_frame_splits_back_to_back_messages() {
+        let mut buf = encode_frame(b"aa");
+        buf.extend_from_slice(&encode_frame(b"bb"));
+        assert_eq!(take_frame(&mut buf), Some(b"aa".to_vec()));
+        assert_eq!(take_frame(&mut buf), Some(b"bb".to_vec()));
+        assert_eq!(take_frame(&mut buf), None);
+    }
+
+    #[test]
+    fn decode_ast_reads_a_serialized_reply() {
+        let ast = parse_ada_to_ast("procedure P;");
+        let body = serde_json::to_vec(&ast).unwrap();
+        assert_eq!(decode_ast(&body).unwrap(), ast);
+    }
+}
@@ -0,0 +1,352 @@
+//! A minimal JSON-RPC 2.0 Language Server for the Ada subset.
+//!
+//! This replaces the old `MixedCombinedParser` socket probe: instead of an
+//! ad-hoc even-length/ISO-9001 string handoff, the server speaks the Language
+//! Server Protocol over a socket. Messages are framed with `Content-Length`
+//! headers followed by a JSON body. Supported requests and notifications:
+//!
+//! * `initialize` / `initialized`
+//! * `textDocument/didOpen` / `textDocument/didChange`
+//! * `textDocument/documentSymbol`
+//! * `textDocument/publishDiagnostics` (sent by the server)
+//!
+//! Parsed ASTs are kept in an in-memory map keyed by document URI so that a
+//! `didChange` simply re-parses the updated text.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, ToSocketAddrs};
+
+use serde_json::{json, Value};
+
+use crate::{parse_ada_to_ast, AstNode, NodeKind};
+
+/// LSP `SymbolKind` values used by `documentSymbol`.
+const SYMBOL_KIND_MODULE: i64 = 2;
+const SYMBOL_KIND_FUNCTION: i64 = 12;
+
+/// LSP `DiagnosticSeverity::Warning`.
+const SEVERITY_WARNING: i64 = 2;
+
+/// A parsed open document: its AST plus the source text it was parsed from,
+/// which we keep so spans can be mapped back to line/column positions.
+struct Document {
+    ast: AstNode,
+    text: String,
+}
+
+/// Tracks the parsed state of every open document.
+#[derive(Default)]
+pub struct LanguageServer {
+    documents: HashMap<String, Document>,
+}
+
+impl LanguageServer {
+    pub fn new() -> LanguageServer {
+        LanguageServer::default()
+    }
+
+    /// Listen for a single LSP client on `addr` and serve it until the
+    /// connection closes. The default handoff used `127.0.0.1`, so callers
+    /// keep the same address.
+    pub fn serve<A: ToSocketAddrs>(&mut self, addr: A) -> std::io::Result<()> {
+        let listener = TcpListener::bind(addr)?;
+        let (stream, _) = listener.accept()?;
+        let mut writer = stream.try_clone()?;
+        let mut reader = BufReader::new(stream);
+        while let Some(message) = read_message(&mut reader)? {
+            for response in self.handle(message) {
+                write_message(&mut writer, &response)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Dispatch one incoming message, returning any responses/notifications
+    /// the server should send back (in order).
+    pub fn handle(&mut self, message: Value) -> Vec<Value> {
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let id = message.get("id").cloned();
+        match method {
+            "initialize" => vec![reply(id, json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "documentSymbolProvider": true,
+                }
+            }))],
+            "initialized" | "shutdown" | "exit" => Vec::new(),
+            "textDocument/didOpen" => {
+                let doc = message.pointer("/params/textDocument");
+                let uri = doc.and_then(|d| d.get("uri")).and_then(Value::as_str).unwrap_or("");
+                let text = doc.and_then(|d| d.get("text")).and_then(Value::as_str).unwrap_or("");
+                self.ingest(uri, text)
+            }
+            "textDocument/didChange" => {
+                let uri = message
+                    .pointer("/params/textDocument/uri")
+                    .and_then(Value::as_str)
+                    .unwrap_or("");
+                // Full-document sync: the last content change holds the whole text.
+                let text = message
+                    .pointer("/params/contentChanges")
+                    .and_then(Value::as_array)
+                    .and_then(|c| c.last())
+                    .and_then(|c| c.get("text"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("");
+                self.ingest(uri, text)
+            }
+            "textDocument/documentSymbol" => {
+                let uri = message
+                    .pointer("/params/textDocument/uri")
+                    .and_then(Value::as_str)
+                    .unwrap_or("");
+                let symbols = self
+                    .documents
+                    .get(uri)
+                    .map(|doc| document_symbols(&doc.ast, &doc.text))
+                    .unwrap_or_default();
+                vec![reply(id, Value::Array(symbols))]
+            }
+            _ => {
+                // Unknown request: answer with a null result so clients unblock.
+                if id.is_some() {
+                    vec![reply(id, Value::Null)]
+                } else {
+                    Vec::new()
+                }
+            }
+        }
+    }
+
+    /// Re-parse `text`, store the AST under `uri`, and emit diagnostics.
+    fn ingest(&mut self, uri: &str, text: &str) -> Vec<Value> {
+        let ast = parse_ada_to_ast(text);
+        let diagnostics = diagnostics(&ast, text);
+        self.documents
+            .insert(uri.to_string(), Document { ast, text: text.to_string() });
+        vec![notify(
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "diagnostics": diagnostics }),
+        )]
+    }
+}
+
+/// Build a JSON-RPC success response for request `id`.
+fn reply(id: Option<Value>, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id.unwrap_or(Value::Null), "result": result })
+}
+
+/// Build a JSON-RPC notification.
+fn notify(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+/// A zero-based LSP `Position` for byte `offset` within `source`. The span's
+/// start line/column alone can't describe a multi-line node, so we derive both
+/// endpoints from the source, counting lines up to the offset.
+fn position(source: &str, offset: usize) -> Value {
+    let offset = offset.min(source.len());
+    let mut line = 0u32;
+    let mut line_start = 0usize;
+    for (idx, b) in source.as_bytes()[..offset].iter().enumerate() {
+        if *b == b'\n' {
+            line += 1;
+            line_start = idx + 1;
+        }
+    }
+    // `character` is a UTF-16 code-unit count (LSP's default encoding). Walk
+    // whole characters from the line start rather than slicing at `offset`,
+    // which a byte offset may split mid-character on non-ASCII input.
+    let mut character = 0u32;
+    for (idx, ch) in source[line_start..].char_indices() {
+        if line_start + idx >= offset {
+            break;
+        }
+        character += ch.len_utf16() as u32;
+    }
+    json!({ "line": line, "character": character })
+}
+
+/// An LSP `Range` spanning a node's byte range, correct across multiple lines.
+fn range(source: &str, node: &AstNode) -> Value {
+    let s = node.span;
+    json!({
+        "start": position(source, s.start),
+        "end": position(source, s.end),
+    })
+}
+
+/// Map `PackageDecl`/`ProcedureDecl`/`FunctionDecl` nodes to `documentSymbol`
+/// entries, recursing into package and subprogram bodies.
+fn document_symbols(node: &AstNode, source: &str) -> Vec<Value> {
+    let mut symbols = Vec::new();
+    for child in &node.children {
+        match &child.kind {
+            NodeKind::PackageDecl => {
+                symbols.push(symbol(name_of(child), SYMBOL_KIND_MODULE, child, source));
+            }
+            NodeKind::ProcedureDecl { name } => {
+                symbols.push(symbol(name, SYMBOL_KIND_FUNCTION, child, source));
+            }
+            NodeKind::FunctionDecl { name, .. } => {
+                symbols.push(symbol(name, SYMBOL_KIND_FUNCTION, child, source));
+            }
+            _ => {}
+        }
+    }
+    symbols
+}
+
+fn symbol(name: &str, kind: i64, node: &AstNode, source: &str) -> Value {
+    let range = range(source, node);
+    json!({
+        "name": name,
+        "kind": kind,
+        "range": range,
+        "selectionRange": range,
+        "children": document_symbols(node, source),
+    })
+}
+
+/// The name of a `PackageDecl`, taken from its first `Identifier` child.
+fn name_of(node: &AstNode) -> &str {
+    node.children
+        .iter()
+        .find_map(|c| match &c.kind {
+            NodeKind::Identifier { name } => Some(name.as_str()),
+            _ => None,
+        })
+        .unwrap_or("<anonymous>")
+}
+
+/// Collect parse-recovery points (`Unknown` nodes with a real span) as
+/// `Diagnostic` objects.
+fn diagnostics(node: &AstNode, source: &str) -> Vec<Value> {
+    let mut out = Vec::new();
+    collect_diagnostics(node, source, &mut out);
+    out
+}
+
+fn collect_diagnostics(node: &AstNode, source: &str, out: &mut Vec<Value>) {
+    if node.kind == NodeKind::Unknown && node.span.end > node.span.start {
+        out.push(json!({
+            "range": range(source, node),
+            "severity": SEVERITY_WARNING,
+            "source": "ada",
+            "message": "skipped malformed declaration during parse recovery",
+        }));
+    }
+    for child in &node.children {
+        collect_diagnostics(child, source, out);
+    }
+}
+
+/// Read one `Content-Length`-framed JSON message, or `None` at end of stream.
+fn read_message<R: BufRead>(reader: &mut R) -> std::io::Result<Option<Value>> {
+    let mut content_length = 0usize;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let trimmed = line.trim_end();
+        if trimmed.is_empty() {
+            break;
+        }
+        if let Some(value) = trimmed.strip_prefix("Content-Length:") {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+    }
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)?;
+    let value = serde_json::from_slice(&body)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    Ok(Some(value))
+}
+
+/// Write one `Content-Length`-framed JSON message.
+fn write_message<W: Write>(writer: &mut W, message: &Value) -> std::io::Result<()> {
+    let body = serde_json::to_vec(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n", body.len())?;
+    writer.write_all(&body)?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initialize_advertises_symbol_provider() {
+        let mut server = LanguageServer::new();
+        let reply = server
+            .handle(json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }))
+            .remove(0);
+        assert_eq!(reply["result"]["capabilities"]["documentSymbolProvider"], json!(true));
+    }
+
+    #[test]
+    fn did_open_publishes_diagnostics_for_recovery() {
+        let mut server = LanguageServer::new();
+        let out = server.handle(json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": "file:///a.adb", "text": "procedure @@@ ;" } }
+        }));
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0]["method"], "textDocument/publishDiagnostics");
+        let diags = out[0]["params"]["diagnostics"].as_array().unwrap();
+        assert!(!diags.is_empty());
+        assert_eq!(diags[0]["severity"], json!(SEVERITY_WARNING));
+    }
+
+    #[test]
+    fn document_symbol_lists_subprograms() {
+        let mut server = LanguageServer::new();
+        server.handle(json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": {
+                "uri": "file:///p.ads",
+                "text": "package P is\n procedure Go;\n function F return Integer;\nend P;"
+            } }
+        }));
+        let reply = server
+            .handle(json!({
+                "jsonrpc": "2.0", "id": 7, "method": "textDocument/documentSymbol",
+                "params": { "textDocument": { "uri": "file:///p.ads" } }
+            }))
+            .remove(0);
+        let symbols = reply["result"].as_array().unwrap();
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0]["name"], "P");
+        let nested = symbols[0]["children"].as_array().unwrap();
+        let names: Vec<_> = nested.iter().map(|s| s["name"].as_str().unwrap()).collect();
+        assert!(names.contains(&"Go"));
+        assert!(names.contains(&"F"));
+    }
+
+    #[test]
+    fn did_change_reparses_stored_document() {
+        let mut server = LanguageServer::new();
+        server.handle(json!({
+            "jsonrpc": "2.0", "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": "file:///a.adb", "text": "procedure Old;" } }
+        }));
+        server.handle(json!({
+            "jsonrpc": "2.0", "method": "textDocument/didChange",
+            "params": {
+                "textDocument": { "uri": "file:///a.adb" },
+                "contentChanges": [ { "text": "procedure New_Name;" } ]
+            }
+        }));
+        let reply = server
+            .handle(json!({
+                "jsonrpc": "2.0", "id": 1, "method": "textDocument/documentSymbol",
+                "params": { "textDocument": { "uri": "file:///a.adb" } }
+            }))
+            .remove(0);
+        assert_eq!(reply["result"][0]["name"], "New_Name");
+    }
+}